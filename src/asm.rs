@@ -0,0 +1,293 @@
+//! A line-oriented assembler for UM-32 programs, the inverse of [`crate::disassemble`].
+//!
+//! Every operator other than `orthography`, `halt` and `load_program` is
+//! written with all three register operands (`name rA, rB, rC`), matching the bit layout that
+//! [`Operator`](crate) always decodes regardless of which fields a given
+//! opcode actually reads. `orthography` takes a register and an immediate
+//! (`orthography rA, 0x1234`), `halt` takes none, and `load_program` takes a
+//! register and a label (`load_program rB, loop_start`) whose offset is
+//! packed into the 3-bit `C` field.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Errors produced while assembling UM-32 source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsmError {
+    UnknownMnemonic {
+        line: usize,
+        mnemonic: String,
+    },
+    WrongOperandCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidRegister {
+        line: usize,
+        operand: String,
+    },
+    InvalidImmediate {
+        line: usize,
+        operand: String,
+    },
+    UndefinedLabel {
+        line: usize,
+        label: String,
+    },
+    DuplicateLabel {
+        line: usize,
+        label: String,
+    },
+    LabelOutOfRange {
+        line: usize,
+        label: String,
+        offset: usize,
+    },
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsmError::UnknownMnemonic { line, mnemonic } => {
+                write!(f, "line {}: unknown operator '{}'", line, mnemonic)
+            }
+            AsmError::WrongOperandCount {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} operand(s), found {}",
+                line, expected, found
+            ),
+            AsmError::InvalidRegister { line, operand } => {
+                write!(f, "line {}: invalid register '{}'", line, operand)
+            }
+            AsmError::InvalidImmediate { line, operand } => {
+                write!(f, "line {}: invalid immediate '{}'", line, operand)
+            }
+            AsmError::UndefinedLabel { line, label } => {
+                write!(f, "line {}: undefined label '{}'", line, label)
+            }
+            AsmError::DuplicateLabel { line, label } => {
+                write!(f, "line {}: label '{}' already defined", line, label)
+            }
+            AsmError::LabelOutOfRange {
+                line,
+                label,
+                offset,
+            } => write!(
+                f,
+                "line {}: label '{}' resolves to offset {}, which does not fit in the 3-bit load_program target field",
+                line, label, offset
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AsmError {}
+
+#[derive(Clone, Copy)]
+enum Operands {
+    Triple,
+    None,
+    Special,
+    LoadProgram,
+}
+
+fn mnemonic_info(name: &str) -> Option<(u32, Operands)> {
+    Some(match name {
+        "conditional_move" => (0, Operands::Triple),
+        "array_index" => (1, Operands::Triple),
+        "array_amendment" => (2, Operands::Triple),
+        "addition" => (3, Operands::Triple),
+        "multiplication" => (4, Operands::Triple),
+        "division" => (5, Operands::Triple),
+        "not_and" => (6, Operands::Triple),
+        "halt" => (7, Operands::None),
+        "allocation" => (8, Operands::Triple),
+        "abandonment" => (9, Operands::Triple),
+        "output" => (10, Operands::Triple),
+        "input" => (11, Operands::Triple),
+        "load_program" => (12, Operands::LoadProgram),
+        "orthography" => (13, Operands::Special),
+        _ => return None,
+    })
+}
+
+/// Assembles `src` into a program scroll, resolving labels declared with
+/// `label:` against the offset of the next instruction.
+pub fn assemble(src: &str) -> Result<Vec<u32>, AsmError> {
+    let mut labels: BTreeMap<String, usize> = BTreeMap::new();
+    let mut instructions: Vec<(usize, &str)> = Vec::new();
+
+    for (idx, raw_line) in src.lines().enumerate() {
+        let line_no = idx + 1;
+        let mut line = strip_comment(raw_line).trim();
+        if let Some(colon) = line.find(':') {
+            let label = line[..colon].trim();
+            if labels
+                .insert(label.to_string(), instructions.len())
+                .is_some()
+            {
+                return Err(AsmError::DuplicateLabel {
+                    line: line_no,
+                    label: label.to_string(),
+                });
+            }
+            line = line[colon + 1..].trim();
+        }
+        if line.is_empty() {
+            continue;
+        }
+        instructions.push((line_no, line));
+    }
+
+    instructions
+        .into_iter()
+        .map(|(line_no, text)| assemble_instruction(line_no, text, &labels))
+        .collect()
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find(';') {
+        Some(idx) => &line[..idx],
+        None => line,
+    }
+}
+
+fn assemble_instruction(
+    line: usize,
+    text: &str,
+    labels: &BTreeMap<String, usize>,
+) -> Result<u32, AsmError> {
+    let (mnemonic, rest) = match text.find(char::is_whitespace) {
+        Some(idx) => (&text[..idx], text[idx..].trim()),
+        None => (text, ""),
+    };
+    let mnemonic = mnemonic.to_lowercase();
+    let (number, kind) = mnemonic_info(&mnemonic).ok_or_else(|| AsmError::UnknownMnemonic {
+        line,
+        mnemonic: mnemonic.clone(),
+    })?;
+    let operands: Vec<&str> = if rest.is_empty() {
+        Vec::new()
+    } else {
+        rest.split(',').map(str::trim).collect()
+    };
+
+    match kind {
+        Operands::None => {
+            expect_operands(line, &operands, 0)?;
+            Ok(number << 28)
+        }
+        Operands::Triple => {
+            expect_operands(line, &operands, 3)?;
+            let a = parse_register(line, operands[0])?;
+            let b = parse_register(line, operands[1])?;
+            let c = parse_register(line, operands[2])?;
+            Ok((number << 28) | (a << 6) | (b << 3) | c)
+        }
+        Operands::Special => {
+            expect_operands(line, &operands, 2)?;
+            let a = parse_register(line, operands[0])?;
+            let value = parse_immediate(line, operands[1])?;
+            Ok((number << 28) | (a << 25) | value)
+        }
+        Operands::LoadProgram => {
+            expect_operands(line, &operands, 2)?;
+            let b = parse_register(line, operands[0])?;
+            let label = operands[1];
+            let offset = *labels.get(label).ok_or_else(|| AsmError::UndefinedLabel {
+                line,
+                label: label.to_string(),
+            })?;
+            if offset > 0b111 {
+                return Err(AsmError::LabelOutOfRange {
+                    line,
+                    label: label.to_string(),
+                    offset,
+                });
+            }
+            Ok((number << 28) | (b << 3) | offset as u32)
+        }
+    }
+}
+
+fn expect_operands(line: usize, operands: &[&str], expected: usize) -> Result<(), AsmError> {
+    if operands.len() != expected {
+        return Err(AsmError::WrongOperandCount {
+            line,
+            expected,
+            found: operands.len(),
+        });
+    }
+    Ok(())
+}
+
+fn parse_register(line: usize, tok: &str) -> Result<u32, AsmError> {
+    tok.strip_prefix('r')
+        .or_else(|| tok.strip_prefix('R'))
+        .and_then(|d| d.parse::<u32>().ok())
+        .filter(|&n| n < 8)
+        .ok_or_else(|| AsmError::InvalidRegister {
+            line,
+            operand: tok.to_string(),
+        })
+}
+
+fn parse_immediate(line: usize, tok: &str) -> Result<u32, AsmError> {
+    let parsed = if let Some(hex) = tok.strip_prefix("0x").or_else(|| tok.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else {
+        tok.parse::<u32>().ok()
+    };
+    parsed
+        .filter(|&v| v <= 0x00ff_ffff)
+        .ok_or_else(|| AsmError::InvalidImmediate {
+            line,
+            operand: tok.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn assembles_conditional_move() {
+        let scroll = assemble("conditional_move r6, r5, r4").unwrap();
+        assert_eq!(scroll, vec![0o600 | 0o50 | 4]);
+    }
+
+    #[test]
+    fn assembles_orthography() {
+        let scroll = assemble("orthography r7, 0xacab").unwrap();
+        assert_eq!(scroll, vec![(13 << 28) | (7 << 25) | 0xacab]);
+    }
+
+    #[test]
+    fn assembles_halt() {
+        assert_eq!(assemble("halt").unwrap(), vec![7 << 28]);
+    }
+
+    #[test]
+    fn resolves_labels_for_load_program() {
+        let src = "halt\nloop_start:\nhalt\nload_program r2, loop_start";
+        let scroll = assemble(src).unwrap();
+        assert_eq!(scroll[2], (12 << 28) | (2 << 3) | 1);
+    }
+
+    #[test]
+    fn rejects_unknown_mnemonic() {
+        assert!(matches!(
+            assemble("frobnicate r0, r1, r2"),
+            Err(AsmError::UnknownMnemonic { line: 1, .. })
+        ));
+    }
+}
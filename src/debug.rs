@@ -0,0 +1,18 @@
+use alloc::vec::Vec;
+
+/// The result of stepping a [`crate::Cpu`] exactly one instruction, for
+/// building inspectable traces on top of [`crate::disassemble`]-style
+/// metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepInfo {
+    /// The program counter the executed instruction was fetched from.
+    pub pc: u32,
+    /// The raw opcode number (0-13).
+    pub opcode: usize,
+    /// The operator's human-readable name, e.g. `"Array Amendment"`.
+    pub name: &'static str,
+    /// Register indices the instruction read or wrote.
+    pub registers: Vec<usize>,
+    /// The array index the instruction operated on, if any.
+    pub array: Option<u32>,
+}
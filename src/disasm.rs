@@ -0,0 +1,115 @@
+use crate::Operator;
+use alloc::vec::Vec;
+
+/// The decoded operand fields of a [`DecodedOp`], which differ between
+/// `orthography` and every other operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodedFields {
+    Triple { a: usize, b: usize, c: usize },
+    Special { a_special: usize, value: u32 },
+}
+
+/// One decoded word of a program scroll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodedOp {
+    /// Offset of this word within the scroll.
+    pub offset: usize,
+    /// The raw opcode number packed into the top 4 bits.
+    pub opcode: usize,
+    /// The operator's human-readable name, e.g. `"Array Amendment"`.
+    pub name: &'static str,
+    pub fields: DecodedFields,
+    /// Set when `opcode` doesn't match any of the 14 defined operators,
+    /// which usually means this word is data (such as an `orthography`
+    /// immediate) rather than a real instruction.
+    pub plausible_immediate: bool,
+}
+
+/// Decodes every word of `scroll` without executing it.
+pub fn disassemble(scroll: &[u32]) -> Vec<DecodedOp> {
+    scroll
+        .iter()
+        .enumerate()
+        .map(|(offset, &word)| {
+            let op = Operator(word);
+            let number = op.number();
+            let fields = if number == 13 {
+                DecodedFields::Special {
+                    a_special: op.A_special(),
+                    value: op.value(),
+                }
+            } else {
+                DecodedFields::Triple {
+                    a: op.A(),
+                    b: op.B(),
+                    c: op.C(),
+                }
+            };
+            DecodedOp {
+                offset,
+                opcode: number,
+                name: op.name(),
+                fields,
+                plausible_immediate: number > 13,
+            }
+        })
+        .collect()
+}
+
+/// Prints `scroll`'s decoded instructions, one per line. This is the `-d`
+/// CLI mode; callers that want structured data should use [`disassemble`].
+#[cfg(feature = "std")]
+pub fn print_disassembly(scroll: &[u32]) {
+    for op in disassemble(scroll) {
+        std::print!("[{}]: {} ({}) | ", op.offset, op.name, op.opcode);
+        match op.fields {
+            DecodedFields::Special { a_special, value } => {
+                std::println!("A: {} | value: {}", a_special, value)
+            }
+            DecodedFields::Triple { a, b, c } => {
+                std::println!("A: {} | B: {} | C: {}", a, b, c)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disassembles_triple_and_special_operands() {
+        let scroll = [
+            (3 << 28) | (1 << 6) | (2 << 3) | 3,
+            (13 << 28) | (7 << 25) | 0xacab,
+        ];
+        let decoded = disassemble(&scroll);
+        assert_eq!(decoded.len(), 2);
+
+        assert_eq!(decoded[0].offset, 0);
+        assert_eq!(decoded[0].opcode, 3);
+        assert_eq!(decoded[0].name, "Addition");
+        assert_eq!(decoded[0].fields, DecodedFields::Triple { a: 1, b: 2, c: 3 });
+        assert!(!decoded[0].plausible_immediate);
+
+        assert_eq!(decoded[1].offset, 1);
+        assert_eq!(decoded[1].opcode, 13);
+        assert_eq!(decoded[1].name, "Orthography");
+        assert_eq!(
+            decoded[1].fields,
+            DecodedFields::Special {
+                a_special: 7,
+                value: 0xacab
+            }
+        );
+        assert!(!decoded[1].plausible_immediate);
+    }
+
+    #[test]
+    fn flags_words_with_no_matching_opcode_as_a_plausible_immediate() {
+        let scroll = [0xf000_0000];
+        let decoded = disassemble(&scroll);
+        assert_eq!(decoded[0].opcode, 15);
+        assert!(decoded[0].plausible_immediate);
+    }
+}
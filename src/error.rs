@@ -0,0 +1,36 @@
+use core::fmt;
+
+/// Errors that can occur while executing a UM-32 program scroll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecError {
+    /// An `array_index` or `array_amendment` offset fell outside the named array.
+    OutOfBoundsArrayIndex { array: u32, offset: u32 },
+    /// An operation referenced an array index that has not been allocated.
+    NoArrayAtIndex(u32),
+    /// `division` attempted to divide by zero.
+    DivideByZero,
+    /// `abandonment` attempted to abandon the 0 array, which holds the running program.
+    AbandonZeroArray,
+    /// The opcode field did not match any of the 14 defined operators.
+    InvalidOpcode(u32),
+    /// Reading or writing a scroll or an I/O opcode failed.
+    IoError,
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecError::OutOfBoundsArrayIndex { array, offset } => {
+                write!(f, "offset {} is out of bounds for array {}", offset, array)
+            }
+            ExecError::NoArrayAtIndex(idx) => write!(f, "no array at index {}", idx),
+            ExecError::DivideByZero => write!(f, "attempt to divide by zero"),
+            ExecError::AbandonZeroArray => write!(f, "attempt to abandon the 0 array"),
+            ExecError::InvalidOpcode(n) => write!(f, "unknown op number: {}", n),
+            ExecError::IoError => write!(f, "I/O error"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ExecError {}
@@ -0,0 +1,38 @@
+use crate::error::ExecError;
+
+/// Pluggable byte I/O for the `output` and `input` opcodes.
+///
+/// Implement this to drive a [`Cpu`](crate::Cpu) from an in-memory buffer, a
+/// socket, or any other byte stream, instead of the process's standard
+/// streams. This is what lets the crate run under `#![no_std]`.
+pub trait Io {
+    fn read_byte(&mut self) -> Result<u8, ExecError>;
+    fn write_byte(&mut self, b: u8) -> Result<(), ExecError>;
+}
+
+/// Default [`Io`] implementation that reads from stdin and writes to stdout.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct StdIo;
+
+#[cfg(feature = "std")]
+impl Io for StdIo {
+    fn read_byte(&mut self) -> Result<u8, ExecError> {
+        use std::io::Read;
+        let mut c = [0u8; 1];
+        std::io::stdin()
+            .lock()
+            .read_exact(&mut c)
+            .map_err(|_| ExecError::IoError)?;
+        Ok(c[0])
+    }
+
+    fn write_byte(&mut self, b: u8) -> Result<(), ExecError> {
+        use std::io::Write;
+        std::io::stdout()
+            .lock()
+            .write(&[b])
+            .map_err(|_| ExecError::IoError)?;
+        Ok(())
+    }
+}
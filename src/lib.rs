@@ -1,31 +1,67 @@
-use itertools::Itertools;
-use std::{
-    collections::HashMap,
-    fs,
-    io::{self, prelude::*},
-    u32,
-};
-
-macro_rules! fail {
-    ($($arg:tt)*) => {
-        {
-            eprint!("fail: ");
-            eprintln!($($arg)*);
-            std::process::exit(1)
-        }
-    };
-}
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::{boxed::Box, collections::BTreeSet, vec, vec::Vec};
 
-#[derive(Debug)]
-pub struct Cpu {
+mod asm;
+mod debug;
+mod disasm;
+mod error;
+mod io;
+mod timer;
+
+pub use asm::{assemble, AsmError};
+pub use debug::StepInfo;
+#[cfg(feature = "std")]
+pub use disasm::print_disassembly;
+pub use disasm::{disassemble, DecodedFields, DecodedOp};
+pub use error::ExecError;
+pub use io::Io;
+#[cfg(feature = "std")]
+pub use io::StdIo;
+use timer::Timer;
+pub use timer::{RunState, TimerAction};
+
+use core::fmt;
+
+pub struct Cpu<IO: Io> {
     regs: [u32; 8],
     pc: u32,
     halted: bool,
-    memory: HashMap<u32, Box<[u32]>>,
+    /// Arrays, keyed by their index into this slab. Slot 0 holds the running
+    /// program. A `None` slot is a hole left by [`abandonment`](Self::abandonment)
+    /// that [`allocation`](Self::allocation) will reuse before growing the slab.
+    memory: Vec<Option<Box<[u32]>>>,
+    /// Free list of abandoned slots, most recently freed first.
     reuse: Vec<u32>,
+    io: IO,
+    counter: u64,
+    timer: Option<Timer<IO>>,
+    breakpoints: BTreeSet<u32>,
+}
+
+impl<IO: Io> fmt::Debug for Cpu<IO> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cpu")
+            .field("regs", &self.regs)
+            .field("pc", &self.pc)
+            .field("halted", &self.halted)
+            .field("counter", &self.counter)
+            .finish_non_exhaustive()
+    }
 }
 
-struct Operator(u32);
+/// A dispatch-table entry: an opcode's handler function.
+type Operation<IO> = fn(&mut Cpu<IO>, Operator) -> Result<(), ExecError>;
+
+/// The only operator that sets `pc` itself; every other operator falls
+/// through to the next word.
+const LOAD_PROGRAM: usize = 12;
+
+pub(crate) struct Operator(u32);
 
 #[allow(non_snake_case)]
 impl Operator {
@@ -74,167 +110,357 @@ impl Operator {
     }
 }
 
-impl Cpu {
-    pub fn new(program_scroll: Vec<u32>) -> Cpu {
+impl<IO: Io> Cpu<IO> {
+    pub fn new(program_scroll: Vec<u32>, io: IO) -> Cpu<IO> {
         Cpu {
             regs: [0, 0, 0, 0, 0, 0, 0, 0],
             pc: 0,
             halted: false,
-            memory: {
-                let mut m = HashMap::new();
-                m.insert(0, program_scroll.into_boxed_slice());
-                m
-            },
+            memory: vec![Some(program_scroll.into_boxed_slice())],
             reuse: vec![],
+            io,
+            counter: 0,
+            timer: None,
+            breakpoints: BTreeSet::new(),
+        }
+    }
+
+    /// The number of instructions executed so far, wrapping on overflow.
+    pub fn instructions_executed(&self) -> u64 {
+        self.counter
+    }
+
+    /// The eight general-purpose registers.
+    pub fn regs(&self) -> &[u32; 8] {
+        &self.regs
+    }
+
+    /// The offset of the next instruction to execute in the program scroll.
+    pub fn pc(&self) -> u32 {
+        self.pc
+    }
+
+    /// The contents of the array at `idx`, if one is currently allocated there.
+    pub fn array(&self, idx: u32) -> Option<&[u32]> {
+        self.memory
+            .get(idx as usize)
+            .and_then(|slot| slot.as_deref())
+    }
+
+    fn array_ref(&self, idx: u32) -> Result<&[u32], ExecError> {
+        self.memory
+            .get(idx as usize)
+            .and_then(|slot| slot.as_deref())
+            .ok_or(ExecError::NoArrayAtIndex(idx))
+    }
+
+    fn array_mut(&mut self, idx: u32) -> Result<&mut [u32], ExecError> {
+        self.memory
+            .get_mut(idx as usize)
+            .and_then(|slot| slot.as_deref_mut())
+            .ok_or(ExecError::NoArrayAtIndex(idx))
+    }
+
+    /// Halts [`spin_cycle`](Self::spin_cycle) and [`spin_cycle_bounded`](Self::spin_cycle_bounded)
+    /// (returning [`RunState::BreakpointHit`]) the next time `pc` equals `offset`.
+    pub fn add_breakpoint(&mut self, offset: u32) {
+        self.breakpoints.insert(offset);
+    }
+
+    /// Removes a previously registered breakpoint. Returns whether it was present.
+    pub fn remove_breakpoint(&mut self, offset: u32) -> bool {
+        self.breakpoints.remove(&offset)
+    }
+
+    /// Registers a callback fired every `period` executed instructions. The
+    /// callback can request a pause by returning [`TimerAction::Pause`],
+    /// which [`spin_cycle_bounded`](Self::spin_cycle_bounded) surfaces as
+    /// [`RunState::Paused`]. Only one timer can be registered at a time;
+    /// calling this again replaces the previous one.
+    pub fn set_timer<F>(&mut self, period: u64, callback: F)
+    where
+        F: FnMut(&Cpu<IO>) -> TimerAction + 'static,
+    {
+        self.timer = Some(Timer {
+            period,
+            callback: Box::new(callback),
+        });
+    }
+
+    /// Runs until the program halts or `pc` hits a breakpoint. Ignores any
+    /// [`TimerAction::Pause`] request from a registered timer, since there is
+    /// no budget to hand control back to; use
+    /// [`spin_cycle_bounded`](Self::spin_cycle_bounded) to actually pause.
+    pub fn spin_cycle(&mut self) -> Result<RunState, ExecError> {
+        loop {
+            match self.run(None)? {
+                RunState::Paused { .. } => continue,
+                other => return Ok(other),
+            }
+        }
+    }
+
+    /// Runs until the program halts, `max_cycles` instructions have executed,
+    /// a timer callback requests a pause, or `pc` hits a breakpoint, so the
+    /// caller can resume later.
+    pub fn spin_cycle_bounded(&mut self, max_cycles: u64) -> Result<RunState, ExecError> {
+        self.run(Some(max_cycles))
+    }
+
+    /// Executes exactly one instruction and reports what it was and which
+    /// registers or array it touched.
+    pub fn step(&mut self) -> Result<StepInfo, ExecError> {
+        let word = self.array_ref(0)?[self.pc as usize];
+        let op = Operator(word);
+        let info = self.step_info(&op);
+        let number = op.number();
+        let operation = Self::operation_for(number)?;
+        operation(self, op)?;
+        if number != LOAD_PROGRAM {
+            self.pc = self.pc.wrapping_add(1);
+        }
+        self.counter = self.counter.wrapping_add(1);
+        Ok(info)
+    }
+
+    fn step_info(&self, op: &Operator) -> StepInfo {
+        let (registers, array) = match op.number() {
+            0 | 3 | 4 | 5 | 6 => (vec![op.A(), op.B(), op.C()], None),
+            1 => (vec![op.A(), op.B(), op.C()], Some(self.regs[op.B()])),
+            2 => (vec![op.A(), op.B(), op.C()], Some(self.regs[op.A()])),
+            7 => (vec![], None),
+            8 => (vec![op.C()], None),
+            9 => (vec![op.C()], Some(self.regs[op.C()])),
+            10 | 11 => (vec![op.C()], None),
+            12 => (vec![op.B()], Some(self.regs[op.B()])),
+            13 => (vec![op.A_special()], None),
+            _ => (vec![], None),
+        };
+        StepInfo {
+            pc: self.pc,
+            opcode: op.number(),
+            name: op.name(),
+            registers,
+            array,
         }
     }
 
-    pub fn spin_cycle(&mut self) {
+    fn operation_for(number: usize) -> Result<Operation<IO>, ExecError> {
+        Ok(match number {
+            0 => Self::conditional_move,
+            1 => Self::array_index,
+            2 => Self::array_amendment,
+            3 => Self::addition,
+            4 => Self::multiplication,
+            5 => Self::division,
+            6 => Self::not_and,
+            7 => Self::halt,
+            8 => Self::allocation,
+            9 => Self::abandonment,
+            10 => Self::output,
+            11 => Self::input,
+            12 => Self::load_program,
+            13 => Self::orthography,
+            n => return Err(ExecError::InvalidOpcode(n as u32)),
+        })
+    }
+
+    fn run(&mut self, max_cycles: Option<u64>) -> Result<RunState, ExecError> {
+        let start = self.counter;
         while !self.halted {
-            let op = self.memory.get(&0).expect("no program scroll")[self.pc as usize];
+            if self.breakpoints.contains(&self.pc) {
+                return Ok(RunState::BreakpointHit { pc: self.pc });
+            }
+            if let Some(max) = max_cycles {
+                let executed = self.counter - start;
+                if executed >= max {
+                    return Ok(RunState::BudgetExhausted { executed });
+                }
+            }
+            let op = self.array_ref(0)?[self.pc as usize];
             let op = Operator(op);
-            let operation = match op.number() {
-                0 => Self::conditional_move,
-                1 => Self::array_index,
-                2 => Self::array_amendment,
-                3 => Self::addition,
-                4 => Self::multiplication,
-                5 => Self::division,
-                6 => Self::not_and,
-                7 => Self::halt,
-                8 => Self::allocation,
-                9 => Self::abandonment,
-                10 => Self::output,
-                11 => Self::input,
-                12 => Self::load_program,
-                13 => Self::orthography,
-                n => fail!("unknown op number: {}", n),
-            };
-            operation(self, op);
+            let number = op.number();
+            let operation = Self::operation_for(number)?;
+            operation(self, op)?;
+            if number != LOAD_PROGRAM {
+                self.pc = self.pc.wrapping_add(1);
+            }
+            self.counter = self.counter.wrapping_add(1);
+
+            if let Some(mut timer) = self.timer.take() {
+                let should_fire = timer.period != 0 && self.counter.is_multiple_of(timer.period);
+                let action = if should_fire {
+                    (timer.callback)(self)
+                } else {
+                    TimerAction::Continue
+                };
+                self.timer = Some(timer);
+                if let TimerAction::Pause = action {
+                    return Ok(RunState::Paused {
+                        executed: self.counter - start,
+                    });
+                }
+            }
         }
+        Ok(RunState::Halted)
     }
 
-    fn conditional_move(&mut self, op: Operator) {
+    fn conditional_move(&mut self, op: Operator) -> Result<(), ExecError> {
         if self.regs[op.C()] != 0 {
             self.regs[op.A()] = self.regs[op.B()]
         }
+        Ok(())
     }
 
-    fn array_index(&mut self, op: Operator) {
+    fn array_index(&mut self, op: Operator) -> Result<(), ExecError> {
         let idx = self.regs[op.B()];
         let offset = self.regs[op.C()];
-        self.regs[op.A()] = self.memory.get(&idx).expect("no array at index")[offset as usize];
+        let array = self.array_ref(idx)?;
+        let value = *array
+            .get(offset as usize)
+            .ok_or(ExecError::OutOfBoundsArrayIndex { array: idx, offset })?;
+        self.regs[op.A()] = value;
+        Ok(())
     }
 
-    fn array_amendment(&mut self, op: Operator) {
+    fn array_amendment(&mut self, op: Operator) -> Result<(), ExecError> {
         let idx = self.regs[op.A()];
         let offset = self.regs[op.B()];
-        self.memory.get_mut(&idx).expect("no array at index")[offset as usize] = self.regs[op.C()];
+        let value = self.regs[op.C()];
+        let array = self.array_mut(idx)?;
+        let slot = array
+            .get_mut(offset as usize)
+            .ok_or(ExecError::OutOfBoundsArrayIndex { array: idx, offset })?;
+        *slot = value;
+        Ok(())
     }
 
-    fn addition(&mut self, op: Operator) {
-        self.regs[op.A()] = (self.regs[op.B()] + self.regs[op.C()]) % u32::MAX;
+    fn addition(&mut self, op: Operator) -> Result<(), ExecError> {
+        self.regs[op.A()] = self.regs[op.B()].wrapping_add(self.regs[op.C()]);
+        Ok(())
     }
 
-    fn multiplication(&mut self, op: Operator) {
-        self.regs[op.A()] = (self.regs[op.B()] * self.regs[op.C()]) % u32::MAX;
+    fn multiplication(&mut self, op: Operator) -> Result<(), ExecError> {
+        self.regs[op.A()] = self.regs[op.B()].wrapping_mul(self.regs[op.C()]);
+        Ok(())
     }
 
-    fn division(&mut self, op: Operator) {
-        self.regs[op.A()] = self.regs[op.B()] / self.regs[op.C()];
+    fn division(&mut self, op: Operator) -> Result<(), ExecError> {
+        let divisor = self.regs[op.C()];
+        if divisor == 0 {
+            return Err(ExecError::DivideByZero);
+        }
+        self.regs[op.A()] = self.regs[op.B()] / divisor;
+        Ok(())
     }
 
-    fn not_and(&mut self, op: Operator) {
+    fn not_and(&mut self, op: Operator) -> Result<(), ExecError> {
         self.regs[op.A()] = !(self.regs[op.B()] & self.regs[op.C()]);
+        Ok(())
     }
 
-    fn halt(&mut self, _op: Operator) {
+    fn halt(&mut self, _op: Operator) -> Result<(), ExecError> {
         self.halted = true;
+        Ok(())
     }
 
-    fn allocation(&mut self, op: Operator) {
-        let idx = if !self.reuse.is_empty() {
-            self.reuse.pop().unwrap()
-        } else {
-            self.memory.len() as u32 + 1
-        };
+    fn allocation(&mut self, op: Operator) -> Result<(), ExecError> {
         let array = vec![0; self.regs[op.C()] as usize].into_boxed_slice();
-        if self.memory.insert(idx, array).is_some() {
-            panic!("BUG: index incorrectly calculated: {} was in use.", idx);
+        if let Some(idx) = self.reuse.pop() {
+            self.memory[idx as usize] = Some(array);
+        } else {
+            self.memory.push(Some(array));
         }
+        Ok(())
     }
 
-    fn abandonment(&mut self, op: Operator) {
+    fn abandonment(&mut self, op: Operator) -> Result<(), ExecError> {
         let idx = self.regs[op.C()];
         if idx == 0 {
-            fail!("attempt to abandon the 0 array");
+            return Err(ExecError::AbandonZeroArray);
         }
-        if self.memory.remove(&idx).is_none() {
-            fail!("removing in-use index {}", idx)
+        let slot = self
+            .memory
+            .get_mut(idx as usize)
+            .ok_or(ExecError::NoArrayAtIndex(idx))?;
+        if slot.take().is_none() {
+            return Err(ExecError::NoArrayAtIndex(idx));
         }
         self.reuse.push(idx);
+        Ok(())
     }
 
-    fn output(&mut self, op: Operator) {
-        io::stdout()
-            .lock()
-            .write(&[self.regs[op.C()] as u8])
-            .expect("Error writing to stdout");
+    fn output(&mut self, op: Operator) -> Result<(), ExecError> {
+        self.io.write_byte(self.regs[op.C()] as u8)
     }
 
-    fn input(&mut self, op: Operator) {
-        let mut c = [0u8; 1];
-        io::stdin()
-            .lock()
-            .read_exact(&mut c)
-            .expect("Could not read from stdin");
-        self.regs[op.C()] = c[0].into();
+    fn input(&mut self, op: Operator) -> Result<(), ExecError> {
+        self.regs[op.C()] = self.io.read_byte()?.into();
+        Ok(())
     }
 
-    fn load_program(&mut self, op: Operator) {
+    fn load_program(&mut self, op: Operator) -> Result<(), ExecError> {
         let idx = self.regs[op.B()];
         let program = self
             .memory
-            .get(&idx)
-            .or_else(|| fail!("no array at index {}", idx))
-            .unwrap()
-            .clone();
-        self.memory.insert(0, program);
-        self.pc = op.C();
+            .get(idx as usize)
+            .and_then(|slot| slot.clone())
+            .ok_or(ExecError::NoArrayAtIndex(idx))?;
+        self.memory[0] = Some(program);
+        self.pc = op.C() as u32;
+        Ok(())
     }
 
-    fn orthography(&mut self, op: Operator) {
+    fn orthography(&mut self, op: Operator) -> Result<(), ExecError> {
         self.regs[op.A_special()] = op.value();
+        Ok(())
     }
 }
 
-pub fn disassemble(scroll: Vec<u32>) {
-    for (i, w) in scroll.into_iter().enumerate() {
-        let op = Operator(w);
-        print!("[{}]: {} ({}) | ", i, op.name(), op.number());
-        if op.number() == 13 {
-            println!("A: {} | value: {}", op.A_special(), op.value())
-        } else {
-            println!("A: {} | B: {} | C: {}", op.A(), op.B(), op.C())
-        }
-    }
-}
+#[cfg(feature = "std")]
+pub fn read_scroll(f: std::fs::File) -> Result<Vec<u32>, ExecError> {
+    use itertools::Itertools;
+    use std::io::{BufReader, Read};
 
-pub fn read_scroll(f: fs::File) -> Vec<u32> {
-    let mut p = Vec::with_capacity((f.metadata().unwrap().len() / 4) as _);
-    for c in f.bytes().chunks(4).into_iter() {
+    let len = f.metadata().map_err(|_| ExecError::IoError)?.len();
+    let mut p = Vec::with_capacity((len / 4) as _);
+    for c in BufReader::new(f).bytes().chunks(4).into_iter() {
         let mut b = [0u8; 4];
-        c.map(|o| o.unwrap())
-            .enumerate()
-            .for_each(|(i, n)| b[i] = n);
+        for (i, byte) in c.enumerate() {
+            b[i] = byte.map_err(|_| ExecError::IoError)?;
+        }
         p.push(u32::from_le_bytes(b));
     }
-    p
+    Ok(p)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Operator;
+    use super::*;
+    use alloc::vec;
+
+    /// An [`Io`] that reads from a fixed input buffer and records writes,
+    /// for tests that don't need a real stdin/stdout.
+    #[derive(Debug, Default)]
+    struct TestIo {
+        input: vec::Vec<u8>,
+        output: vec::Vec<u8>,
+    }
+
+    impl Io for TestIo {
+        fn read_byte(&mut self) -> Result<u8, ExecError> {
+            if self.input.is_empty() {
+                return Err(ExecError::IoError);
+            }
+            Ok(self.input.remove(0))
+        }
+
+        fn write_byte(&mut self, b: u8) -> Result<(), ExecError> {
+            self.output.push(b);
+            Ok(())
+        }
+    }
+
     #[test]
     fn test_operator() {
         let op = Operator(0xe0000000 | 0o600 | 0o50 | 4);
@@ -255,4 +481,160 @@ mod tests {
         assert_eq!(op.A_special(), 7, "op.A_special(): {:x}", op.A_special());
         assert_eq!(op.value(), 0xacab);
     }
+
+    /// `orthography r1, 1; orthography r2, 3; addition r0, r1, r2; halt`
+    fn addition_scroll() -> Vec<u32> {
+        vec![
+            (13 << 28) | (1 << 25) | 1,
+            (13 << 28) | (2 << 25) | 3,
+            (3 << 28) | (1 << 3) | 2,
+            7 << 28,
+        ]
+    }
+
+    #[test]
+    fn spin_cycle_runs_past_the_first_instruction() {
+        let mut cpu = Cpu::new(addition_scroll(), TestIo::default());
+        assert_eq!(cpu.spin_cycle().unwrap(), RunState::Halted);
+        assert_eq!(cpu.regs()[0], 4);
+    }
+
+    #[test]
+    fn step_advances_pc_and_reports_the_instruction() {
+        let mut cpu = Cpu::new(addition_scroll(), TestIo::default());
+        let info = cpu.step().unwrap();
+        assert_eq!(info.pc, 0);
+        assert_eq!(info.opcode, 13);
+        assert_eq!(cpu.pc(), 1);
+    }
+
+    #[test]
+    fn breakpoint_stops_spin_cycle_before_the_marked_instruction() {
+        let mut cpu = Cpu::new(addition_scroll(), TestIo::default());
+        cpu.add_breakpoint(2);
+        assert_eq!(cpu.spin_cycle().unwrap(), RunState::BreakpointHit { pc: 2 });
+        assert_eq!(cpu.regs()[1], 1);
+        assert_eq!(cpu.regs()[0], 0);
+    }
+
+    #[test]
+    fn spin_cycle_bounded_tracks_a_fresh_budget_per_call() {
+        let scroll = vec![
+            (13 << 28) | (1 << 25) | 1,
+            (13 << 28) | (2 << 25) | 1,
+            (13 << 28) | (3 << 25) | 1,
+            (13 << 28) | (4 << 25) | 1,
+            7 << 28,
+        ];
+        let mut cpu = Cpu::new(scroll, TestIo::default());
+        assert_eq!(
+            cpu.spin_cycle_bounded(2).unwrap(),
+            RunState::BudgetExhausted { executed: 2 }
+        );
+        assert_eq!(cpu.pc(), 2);
+        assert_eq!(
+            cpu.spin_cycle_bounded(2).unwrap(),
+            RunState::BudgetExhausted { executed: 2 }
+        );
+        assert_eq!(cpu.pc(), 4);
+    }
+
+    #[test]
+    fn timer_fires_every_period_and_can_pause() {
+        let mut cpu = Cpu::new(addition_scroll(), TestIo::default());
+        cpu.set_timer(2, |_| TimerAction::Pause);
+        assert_eq!(
+            cpu.spin_cycle_bounded(10).unwrap(),
+            RunState::Paused { executed: 2 }
+        );
+        assert_eq!(cpu.pc(), 2);
+    }
+
+    #[test]
+    fn allocation_reuses_abandoned_slots() {
+        let mut cpu = Cpu::new(vec![7 << 28], TestIo::default());
+        cpu.regs[0] = 4;
+        cpu.allocation(Operator(0)).unwrap();
+        assert!(cpu.array(1).is_some());
+        cpu.regs[2] = 1;
+        cpu.abandonment(Operator(2)).unwrap();
+        assert!(cpu.array(1).is_none());
+        cpu.regs[0] = 5;
+        cpu.allocation(Operator(0)).unwrap();
+        assert_eq!(cpu.array(1).unwrap().len(), 5);
+        assert!(cpu.array(2).is_none());
+    }
+
+    #[test]
+    fn addition_and_multiplication_wrap_on_overflow() {
+        let mut cpu = Cpu::new(vec![7 << 28], TestIo::default());
+        cpu.regs[1] = u32::MAX - 1;
+        cpu.regs[2] = 5;
+        cpu.addition(Operator((1 << 3) | 2)).unwrap();
+        assert_eq!(cpu.regs[0], 3);
+
+        cpu.regs[1] = u32::MAX;
+        cpu.regs[2] = 2;
+        cpu.multiplication(Operator((1 << 3) | 2)).unwrap();
+        assert_eq!(cpu.regs[0], u32::MAX.wrapping_mul(2));
+    }
+
+    #[test]
+    fn division_by_zero_returns_err_instead_of_panicking() {
+        let mut cpu = Cpu::new(vec![7 << 28], TestIo::default());
+        cpu.regs[1] = 10;
+        cpu.regs[2] = 0;
+        assert_eq!(
+            cpu.division(Operator((1 << 3) | 2)),
+            Err(ExecError::DivideByZero)
+        );
+    }
+
+    #[test]
+    fn array_index_out_of_bounds_returns_err() {
+        let mut cpu = Cpu::new(vec![7 << 28], TestIo::default());
+        cpu.regs[1] = 0;
+        cpu.regs[2] = 5;
+        assert_eq!(
+            cpu.array_index(Operator((1 << 3) | 2)),
+            Err(ExecError::OutOfBoundsArrayIndex { array: 0, offset: 5 })
+        );
+    }
+
+    #[test]
+    fn array_amendment_out_of_bounds_returns_err() {
+        let mut cpu = Cpu::new(vec![7 << 28], TestIo::default());
+        cpu.regs[1] = 5;
+        assert_eq!(
+            cpu.array_amendment(Operator(1 << 3)),
+            Err(ExecError::OutOfBoundsArrayIndex { array: 0, offset: 5 })
+        );
+    }
+
+    #[test]
+    fn abandoning_the_zero_array_returns_err() {
+        let mut cpu = Cpu::new(vec![7 << 28], TestIo::default());
+        assert_eq!(
+            cpu.abandonment(Operator(0)),
+            Err(ExecError::AbandonZeroArray)
+        );
+    }
+
+    #[test]
+    fn abandoning_an_unallocated_index_returns_err() {
+        let mut cpu = Cpu::new(vec![7 << 28], TestIo::default());
+        cpu.regs[2] = 1;
+        assert_eq!(
+            cpu.abandonment(Operator(2)),
+            Err(ExecError::NoArrayAtIndex(1))
+        );
+    }
+
+    #[test]
+    fn invalid_opcode_returns_err() {
+        assert_eq!(
+            Cpu::<TestIo>::operation_for(14),
+            Err(ExecError::InvalidOpcode(14))
+        );
+    }
 }
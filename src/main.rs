@@ -1,5 +1,5 @@
 use std::{env, fs};
-use um32::{read_scroll, Cpu};
+use um32::{print_disassembly, read_scroll, Cpu, StdIo};
 
 enum Mode {
     Execute,
@@ -23,11 +23,18 @@ fn main() {
     };
     let scroll = fs::File::open(args[2].clone()).unwrap();
     let opts = Opts { mode, scroll };
+    let scroll = read_scroll(opts.scroll).unwrap_or_else(|e| {
+        eprintln!("fail: {}", e);
+        std::process::exit(1);
+    });
     match opts.mode {
         Mode::Execute => {
-            let mut cpu = Cpu::new(read_scroll(opts.scroll));
-            cpu.spin_cycle();
+            let mut cpu = Cpu::new(scroll, StdIo);
+            if let Err(e) = cpu.spin_cycle() {
+                eprintln!("fail: {}", e);
+                std::process::exit(1);
+            }
         }
-        Mode::Disassemble => um32::disassemble(read_scroll(opts.scroll)),
+        Mode::Disassemble => print_disassembly(&scroll),
     }
 }
@@ -0,0 +1,34 @@
+use crate::{Cpu, Io};
+use alloc::boxed::Box;
+
+/// What a timer callback registered with [`Cpu::set_timer`] requests after it runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerAction {
+    /// Keep running.
+    Continue,
+    /// Stop execution and hand control back to the caller.
+    Pause,
+}
+
+/// The outcome of a bounded or callback-driven run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunState {
+    /// The program executed a `halt` instruction.
+    Halted,
+    /// The cycle budget passed to [`Cpu::spin_cycle_bounded`] was reached.
+    BudgetExhausted { executed: u64 },
+    /// A timer callback requested a pause.
+    Paused { executed: u64 },
+    /// Execution stopped because `pc` hit a registered breakpoint.
+    BreakpointHit { pc: u32 },
+}
+
+/// A timer callback, boxed so [`Cpu`] can hold one without a generic closure
+/// parameter of its own.
+pub(crate) type TimerCallback<IO> = Box<dyn FnMut(&Cpu<IO>) -> TimerAction>;
+
+/// A periodic callback fired every `period` executed instructions.
+pub(crate) struct Timer<IO: Io> {
+    pub(crate) period: u64,
+    pub(crate) callback: TimerCallback<IO>,
+}